@@ -1,4 +1,11 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    f32::consts::TAU,
+};
+
 use bevy::{
+    input::mouse::{MouseMotion, MouseWheel},
     prelude::*,
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
     window::PrimaryWindow,
@@ -32,6 +39,62 @@ struct DeselectEvent;
 #[derive(Event)]
 struct MoveToEvent(MoveTo);
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Action {
+    Select,
+    AddToSelection,
+    MoveOrder,
+    Deselect,
+    CancelOrder,
+    ToggleGrid,
+    ToggleSnap,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Binding {
+    Mouse(MouseButton),
+    Key(KeyCode),
+}
+
+#[derive(Resource)]
+struct Bindings(HashMap<Action, Binding>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self(HashMap::from([
+            (Action::Select, Binding::Mouse(MouseButton::Left)),
+            (Action::AddToSelection, Binding::Key(KeyCode::ShiftLeft)),
+            (Action::MoveOrder, Binding::Mouse(MouseButton::Right)),
+            (Action::Deselect, Binding::Mouse(MouseButton::Right)),
+            (Action::CancelOrder, Binding::Key(KeyCode::Escape)),
+            (Action::ToggleGrid, Binding::Key(KeyCode::KeyG)),
+            (Action::ToggleSnap, Binding::Key(KeyCode::KeyN)),
+        ]))
+    }
+}
+
+#[derive(Resource, Default)]
+struct ActionState {
+    just_pressed: HashSet<Action>,
+    pressed: HashSet<Action>,
+    just_released: HashSet<Action>,
+    cursor_world_position: Option<Vec2>,
+}
+
+impl ActionState {
+    fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed.contains(&action)
+    }
+
+    fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    fn just_released(&self, action: Action) -> bool {
+        self.just_released.contains(&action)
+    }
+}
+
 #[derive(Component)]
 struct SelectionDisplay;
 
@@ -50,16 +113,396 @@ struct MoveTo(Vec2);
 #[derive(Component)]
 struct TroopVelocity(f32);
 
+#[derive(Component)]
+struct Obstacle {
+    half_extents: Vec2,
+}
+
+#[derive(Component)]
+struct Hoverable;
+
+#[derive(Component)]
+struct Hovered;
+
+#[derive(Component)]
+struct Draggable;
+
+#[derive(Component)]
+struct Dragged;
+
+#[derive(Component)]
+struct Dropped;
+
+#[derive(Component)]
+struct CameraController {
+    pan_speed: f32,
+    zoom_speed: f32,
+    min_zoom: f32,
+    max_zoom: f32,
+    edge_scroll_margin: f32,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            pan_speed: 500.,
+            zoom_speed: 0.1,
+            min_zoom: 0.25,
+            max_zoom: 4.,
+            edge_scroll_margin: 16.,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+struct Path(VecDeque<Vec2>);
+
 const CIRCLE_RADIUS: f32 = 10.0;
 const BORDER_OFFSET: f32 = 1.;
-const DISTANCE_TOLERANCE: f32 = 1. / (1 >> 8) as f32;
+const DISTANCE_TOLERANCE: f32 = 1.0 / (1u32 << 8) as f32;
+
+const NAV_CELL_SIZE: f32 = 20.0;
+const NAV_GRID_WIDTH: usize = 64;
+const NAV_GRID_HEIGHT: usize = 64;
+
+const FORMATION_SPACING: f32 = CIRCLE_RADIUS * 3.0;
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+struct GridSettings {
+    cell_size: f32,
+    visible: bool,
+    snap_enabled: bool,
+}
+
+impl Default for GridSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 40.,
+            visible: true,
+            snap_enabled: true,
+        }
+    }
+}
+
+impl GridSettings {
+    fn snap(&self, position: Vec2) -> Vec2 {
+        if !self.snap_enabled {
+            return position;
+        }
+
+        (position / self.cell_size).round() * self.cell_size
+    }
+}
+
+#[derive(Resource)]
+struct NavGrid {
+    walkable: Vec<bool>,
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        Self {
+            walkable: vec![true; NAV_GRID_WIDTH * NAV_GRID_HEIGHT],
+        }
+    }
+}
+
+impl NavGrid {
+    fn index(cell: IVec2) -> Option<usize> {
+        if cell.x < 0 || cell.y < 0 || cell.x >= NAV_GRID_WIDTH as i32 || cell.y >= NAV_GRID_HEIGHT as i32 {
+            return None;
+        }
+
+        Some(cell.y as usize * NAV_GRID_WIDTH + cell.x as usize)
+    }
+
+    fn is_walkable(&self, cell: IVec2) -> bool {
+        Self::index(cell).map(|i| self.walkable[i]).unwrap_or(false)
+    }
+
+    fn world_to_cell(world: Vec2) -> IVec2 {
+        let half_extent = Vec2::new(NAV_GRID_WIDTH as f32, NAV_GRID_HEIGHT as f32) * NAV_CELL_SIZE / 2.;
+        let local = (world + half_extent) / NAV_CELL_SIZE;
+
+        IVec2::new(local.x.floor() as i32, local.y.floor() as i32)
+    }
+
+    fn cell_to_world(cell: IVec2) -> Vec2 {
+        let half_extent = Vec2::new(NAV_GRID_WIDTH as f32, NAV_GRID_HEIGHT as f32) * NAV_CELL_SIZE / 2.;
+
+        (cell.as_vec2() + 0.5) * NAV_CELL_SIZE - half_extent
+    }
+
+    fn nearest_walkable(&self, cell: IVec2) -> IVec2 {
+        if self.is_walkable(cell) {
+            return cell;
+        }
+
+        let max_radius = NAV_GRID_WIDTH.max(NAV_GRID_HEIGHT) as i32;
+        for radius in 1..max_radius {
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    if dx.abs() != radius && dy.abs() != radius {
+                        continue;
+                    }
+
+                    let candidate = cell + IVec2::new(dx, dy);
+                    if self.is_walkable(candidate) {
+                        return candidate;
+                    }
+                }
+            }
+        }
+
+        cell
+    }
+
+    // Bresenham's line algorithm, treated as blocked if any traversed cell isn't walkable.
+    fn line_of_sight(&self, from: IVec2, to: IVec2) -> bool {
+        let (mut x0, mut y0) = (from.x, from.y);
+        let (x1, y1) = (to.x, to.y);
+
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+
+        loop {
+            if !self.is_walkable(IVec2::new(x0, y0)) {
+                return false;
+            }
+
+            if x0 == x1 && y0 == y1 {
+                return true;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x0 += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y0 += sy;
+            }
+        }
+    }
+}
+
+struct OpenEntry {
+    f: f32,
+    cell: IVec2,
+}
+
+impl PartialEq for OpenEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.f == other.f
+    }
+}
+
+impl Eq for OpenEntry {}
+
+impl PartialOrd for OpenEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap`, a max-heap, pops the lowest `f` first.
+        other.f.partial_cmp(&self.f).unwrap_or(Ordering::Equal)
+    }
+}
+
+fn cell_neighbors(cell: IVec2) -> [IVec2; 8] {
+    [
+        cell + IVec2::new(1, 0),
+        cell + IVec2::new(-1, 0),
+        cell + IVec2::new(0, 1),
+        cell + IVec2::new(0, -1),
+        cell + IVec2::new(1, 1),
+        cell + IVec2::new(1, -1),
+        cell + IVec2::new(-1, 1),
+        cell + IVec2::new(-1, -1),
+    ]
+}
+
+fn find_path(nav_grid: &NavGrid, start: IVec2, goal: IVec2) -> Option<Vec<IVec2>> {
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut g_score: HashMap<IVec2, f32> = HashMap::new();
+    let mut closed: HashSet<IVec2> = HashSet::new();
+
+    g_score.insert(start, 0.0);
+    open.push(OpenEntry {
+        f: start.as_vec2().distance(goal.as_vec2()),
+        cell: start,
+    });
+
+    while let Some(OpenEntry { cell, .. }) = open.pop() {
+        if cell == goal {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(&previous) = came_from.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+
+            return Some(path);
+        }
+
+        if !closed.insert(cell) {
+            continue;
+        }
+
+        for neighbor in cell_neighbors(cell) {
+            if closed.contains(&neighbor) || !nav_grid.is_walkable(neighbor) {
+                continue;
+            }
+
+            let step_cost = cell.as_vec2().distance(neighbor.as_vec2());
+            let tentative_g = g_score.get(&cell).copied().unwrap_or(f32::INFINITY) + step_cost;
+
+            if tentative_g < g_score.get(&neighbor).copied().unwrap_or(f32::INFINITY) {
+                came_from.insert(neighbor, cell);
+                g_score.insert(neighbor, tentative_g);
+                open.push(OpenEntry {
+                    f: tentative_g + neighbor.as_vec2().distance(goal.as_vec2()),
+                    cell: neighbor,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+// String-pulling: drop any waypoint whose removal still leaves line of sight between its neighbors.
+fn simplify_path(nav_grid: &NavGrid, path: &[IVec2]) -> Vec<IVec2> {
+    if path.len() <= 2 {
+        return path.to_vec();
+    }
+
+    let mut simplified = vec![path[0]];
+    let mut anchor = 0;
+
+    for i in 1..path.len() - 1 {
+        if !nav_grid.line_of_sight(path[anchor], path[i + 1]) {
+            simplified.push(path[i]);
+            anchor = i;
+        }
+    }
+
+    simplified.push(path[path.len() - 1]);
+
+    simplified
+}
+
+#[derive(Resource, Reflect, Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[reflect(Resource)]
+enum FormationKind {
+    #[default]
+    Grid,
+    Circle,
+}
+
+// Lays `count` slots out in a square grid centered on the origin so a group order spreads
+// units apart instead of stacking them on the same goal cell.
+fn grid_offsets(count: usize) -> Vec<Vec2> {
+    let side = (count as f32).sqrt().ceil() as i32;
+    let rows = (count as i32 + side - 1) / side;
+    let half_rows = (rows - 1) as f32 / 2.;
+
+    (0..count as i32)
+        .map(|i| {
+            let col = i % side;
+            let row = i / side;
+            // The last row may be partly filled; center it on its own slot count.
+            let cols_in_row = (count as i32 - row * side).min(side);
+            let half_cols = (cols_in_row - 1) as f32 / 2.;
+            Vec2::new(col as f32 - half_cols, row as f32 - half_rows) * FORMATION_SPACING
+        })
+        .collect()
+}
+
+// Lays `count` slots out on concentric rings, each ring holding as many evenly-spaced slots
+// as fit at `FORMATION_SPACING` apart.
+fn circle_offsets(count: usize) -> Vec<Vec2> {
+    let mut offsets = Vec::with_capacity(count);
+    let mut ring = 1;
+
+    while offsets.len() < count {
+        let radius = ring as f32 * FORMATION_SPACING;
+        let slots_in_ring = ((TAU * radius) / FORMATION_SPACING).round().max(1.) as usize;
+        let slots_this_ring = slots_in_ring.min(count - offsets.len());
+
+        for i in 0..slots_this_ring {
+            let angle = i as f32 / slots_in_ring as f32 * TAU;
+            offsets.push(Vec2::new(angle.cos(), angle.sin()) * radius);
+        }
+
+        ring += 1;
+    }
+
+    offsets
+}
+
+fn formation_offsets(kind: FormationKind, count: usize) -> Vec<Vec2> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    match kind {
+        FormationKind::Grid => grid_offsets(count),
+        FormationKind::Circle => circle_offsets(count),
+    }
+}
+
+// Greedily pairs units with slots in ascending order of distance, so the closest unit/slot
+// pair always wins first. This keeps paths short and avoids crossing routes for off-center
+// groups, unlike a fixed angle-based assignment.
+fn assign_formation_slots(units: &[Vec2], slots: &[Vec2]) -> Vec<Vec2> {
+    let mut candidates: Vec<(usize, usize, f32)> = Vec::with_capacity(units.len() * slots.len());
+    for (unit_index, unit) in units.iter().enumerate() {
+        for (slot_index, slot) in slots.iter().enumerate() {
+            candidates.push((unit_index, slot_index, unit.distance(*slot)));
+        }
+    }
+
+    candidates.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+
+    let mut unit_taken = vec![false; units.len()];
+    let mut slot_taken = vec![false; slots.len()];
+    let mut assignment = vec![None; units.len()];
+
+    for (unit_index, slot_index, _) in candidates {
+        if unit_taken[unit_index] || slot_taken[slot_index] {
+            continue;
+        }
+
+        unit_taken[unit_index] = true;
+        slot_taken[slot_index] = true;
+        assignment[unit_index] = Some(slots[slot_index]);
+    }
+
+    assignment
+        .into_iter()
+        .zip(units)
+        .map(|(slot, unit)| slot.unwrap_or(*unit))
+        .collect()
+}
 
 fn setup(
     mut cmd: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    cmd.spawn(Camera2dBundle::default());
+    cmd.spawn(Camera2dBundle::default())
+        .insert(CameraController::default());
     let circle_mesh = Mesh2dHandle(meshes.add(Circle {
         radius: CIRCLE_RADIUS,
     }));
@@ -73,16 +516,30 @@ fn setup(
         ..default()
     })
     .insert(Selectable)
-    .insert(TroopVelocity(1.0));
+    .insert(TroopVelocity(150.0))
+    .insert(Hoverable)
+    .insert(Draggable);
 
     cmd.spawn(MaterialMesh2dBundle {
-        mesh: rectangle_mesh,
+        mesh: rectangle_mesh.clone(),
         material: materials.add(Color::linear_rgba(0.0, 0.0, 1.0, 0.5)),
         visibility: Visibility::Hidden,
         ..default()
     })
     .insert(SelectionDisplay);
 
+    let obstacle_half_extents = Vec2::new(20., 60.);
+    cmd.spawn(MaterialMesh2dBundle {
+        mesh: rectangle_mesh,
+        material: materials.add(Color::linear_rgba(0.5, 0.5, 0.5, 1.0)),
+        transform: Transform::from_translation(Vec3::new(50., 0., 0.))
+            .with_scale((obstacle_half_extents * 2.).extend(1.)),
+        ..default()
+    })
+    .insert(Obstacle {
+        half_extents: obstacle_half_extents,
+    });
+
     cmd.spawn((
         UiTreeBundle::<MainUi> {
             tree: UiTree::new2d("MainUiSystem"),
@@ -101,49 +558,419 @@ fn setup(
     });
 }
 
-fn handle_mouse_input(
+fn camera_controller(
     windows_query: Query<&Window, With<PrimaryWindow>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut camera_query: Query<(&CameraController, &mut Transform, &mut OrthographicProjection)>,
+    time: Res<Time>,
+) {
+    let Ok((controller, mut transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let mut pan = Vec2::ZERO;
+
+    if keys.pressed(KeyCode::KeyW) || keys.pressed(KeyCode::ArrowUp) {
+        pan.y += 1.;
+    }
+    if keys.pressed(KeyCode::KeyS) || keys.pressed(KeyCode::ArrowDown) {
+        pan.y -= 1.;
+    }
+    if keys.pressed(KeyCode::KeyD) || keys.pressed(KeyCode::ArrowRight) {
+        pan.x += 1.;
+    }
+    if keys.pressed(KeyCode::KeyA) || keys.pressed(KeyCode::ArrowLeft) {
+        pan.x -= 1.;
+    }
+
+    if let Ok(window) = windows_query.get_single() {
+        if let Some(cursor) = window.cursor_position() {
+            let size = window.size();
+
+            if cursor.x <= controller.edge_scroll_margin {
+                pan.x -= 1.;
+            } else if cursor.x >= size.x - controller.edge_scroll_margin {
+                pan.x += 1.;
+            }
+
+            if cursor.y <= controller.edge_scroll_margin {
+                pan.y += 1.;
+            } else if cursor.y >= size.y - controller.edge_scroll_margin {
+                pan.y -= 1.;
+            }
+        }
+    }
+
+    if pan != Vec2::ZERO {
+        let movement = pan.normalize() * controller.pan_speed * projection.scale * time.delta_seconds();
+        transform.translation += movement.extend(0.);
+    }
+
+    if mouse_buttons.pressed(MouseButton::Middle) {
+        for motion in mouse_motion_events.read() {
+            transform.translation.x -= motion.delta.x * projection.scale;
+            transform.translation.y += motion.delta.y * projection.scale;
+        }
+    } else {
+        mouse_motion_events.clear();
+    }
+
+    for wheel in mouse_wheel_events.read() {
+        projection.scale =
+            (projection.scale - wheel.y * controller.zoom_speed).clamp(controller.min_zoom, controller.max_zoom);
+    }
+}
+
+fn update_action_state(
+    windows_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<CameraController>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<Bindings>,
+    mut action_state: ResMut<ActionState>,
+) {
+    action_state.just_pressed.clear();
+    action_state.pressed.clear();
+    action_state.just_released.clear();
+
+    for (&action, binding) in &bindings.0 {
+        let (just_pressed, pressed, just_released) = match *binding {
+            Binding::Mouse(button) => (
+                mouse_buttons.just_pressed(button),
+                mouse_buttons.pressed(button),
+                mouse_buttons.just_released(button),
+            ),
+            Binding::Key(key) => (keys.just_pressed(key), keys.pressed(key), keys.just_released(key)),
+        };
+
+        if just_pressed {
+            action_state.just_pressed.insert(action);
+        }
+        if pressed {
+            action_state.pressed.insert(action);
+        }
+        if just_released {
+            action_state.just_released.insert(action);
+        }
+    }
+
+    let (camera, camera_transform) = camera_query.single();
+    action_state.cursor_world_position = windows_query
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
+}
+
+fn handle_input(
+    action_state: Res<ActionState>,
+    grid_settings: Res<GridSettings>,
     mut selection_box: ResMut<SelectionBox>,
-    buttons: Res<ButtonInput<MouseButton>>,
     mut finished_selecting_event_writer: EventWriter<FinishedSelectingEvent>,
     mut deselect_event_writer: EventWriter<DeselectEvent>,
+    mut move_to_event_writer: EventWriter<MoveToEvent>,
+    selected_query: Query<Entity, With<Selected>>,
+    dragged_query: Query<Entity, With<Dragged>>,
 ) {
-    let Some(window_position) = windows_query.single().cursor_position() else {
+    let Some(world_position) = action_state.cursor_world_position else {
         return;
     };
-    let world_position = window_position - windows_query.single().size() / 2.;
-    let world_position = Vec2::new(world_position.x, -world_position.y);
 
-    if buttons.just_pressed(MouseButton::Left) {
+    if action_state.just_pressed(Action::Select) {
         selection_box.0 = Some((world_position, world_position));
     }
 
-    if buttons.pressed(MouseButton::Left) {
+    if action_state.pressed(Action::Select) {
         if let Some(selection) = selection_box.0 {
             selection_box.0 = Some((selection.0, world_position));
         }
     }
 
-    if buttons.just_released(MouseButton::Left) {
-        finished_selecting_event_writer.send(FinishedSelectingEvent(*selection_box));
+    if action_state.just_released(Action::Select) {
+        // A release that ends a drag is direct manipulation, not a box-select, and must
+        // not clear the current selection.
+        if dragged_query.is_empty() {
+            finished_selecting_event_writer.send(FinishedSelectingEvent(*selection_box));
+        }
         *selection_box = SelectionBox(None);
     }
 
-    if buttons.just_pressed(MouseButton::Right) {
+    if action_state.just_pressed(Action::MoveOrder) && !selected_query.is_empty() {
+        move_to_event_writer.send(MoveToEvent(MoveTo(grid_settings.snap(world_position))));
+        *selection_box = SelectionBox(None);
+    } else if action_state.just_pressed(Action::Deselect) {
         deselect_event_writer.send(DeselectEvent);
         *selection_box = SelectionBox(None);
     }
 }
 
+fn toggle_grid(action_state: Res<ActionState>, mut grid_settings: ResMut<GridSettings>) {
+    if action_state.just_pressed(Action::ToggleGrid) {
+        grid_settings.visible = !grid_settings.visible;
+    }
+
+    if action_state.just_pressed(Action::ToggleSnap) {
+        grid_settings.snap_enabled = !grid_settings.snap_enabled;
+    }
+}
+
+fn draw_grid(
+    mut gizmos: Gizmos,
+    grid_settings: Res<GridSettings>,
+    windows_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<CameraController>>,
+) {
+    if !grid_settings.visible {
+        return;
+    }
+
+    let Ok(window) = windows_query.get_single() else {
+        return;
+    };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let cell_size = grid_settings.cell_size.max(1.);
+    let half_extent = window.size() / 2. * projection.scale;
+    let center = camera_transform.translation.xy();
+    let min = center - half_extent;
+    let max = center + half_extent;
+    let color = Color::linear_rgba(1.0, 1.0, 1.0, 0.1);
+
+    let first_column = (min.x / cell_size).floor() as i32;
+    let last_column = (max.x / cell_size).ceil() as i32;
+    for i in first_column..=last_column {
+        let x = i as f32 * cell_size;
+        gizmos.line_2d(Vec2::new(x, min.y), Vec2::new(x, max.y), color);
+    }
+
+    let first_row = (min.y / cell_size).floor() as i32;
+    let last_row = (max.y / cell_size).ceil() as i32;
+    for i in first_row..=last_row {
+        let y = i as f32 * cell_size;
+        gizmos.line_2d(Vec2::new(min.x, y), Vec2::new(max.x, y), color);
+    }
+}
+
+fn update_hover(
+    action_state: Res<ActionState>,
+    hoverable_query: Query<(Entity, &Transform), With<Hoverable>>,
+    mut cmd: Commands,
+) {
+    let cursor = action_state.cursor_world_position;
+
+    for (entity, transform) in &hoverable_query {
+        let is_hovered = cursor
+            .map(|cursor| transform.translation.xy().distance(cursor) <= CIRCLE_RADIUS)
+            .unwrap_or(false);
+
+        if is_hovered {
+            cmd.entity(entity).insert(Hovered);
+        } else {
+            cmd.entity(entity).remove::<Hovered>();
+        }
+    }
+}
+
+fn begin_drag(
+    action_state: Res<ActionState>,
+    hovered_query: Query<Entity, (With<Hovered>, With<Draggable>, Without<Dragged>)>,
+    mut cmd: Commands,
+) {
+    if !action_state.just_pressed(Action::Select) {
+        return;
+    }
+
+    let Some(entity) = hovered_query.iter().next() else {
+        return;
+    };
+
+    cmd.entity(entity).insert(Dragged);
+}
+
+fn cancel_selection_box_on_drag(
+    new_drags_query: Query<Entity, Added<Dragged>>,
+    mut selection_box: ResMut<SelectionBox>,
+) {
+    if !new_drags_query.is_empty() {
+        *selection_box = SelectionBox(None);
+    }
+}
+
+fn drag_entities(action_state: Res<ActionState>, mut dragged_query: Query<&mut Transform, With<Dragged>>) {
+    let Some(cursor) = action_state.cursor_world_position else {
+        return;
+    };
+
+    for mut transform in &mut dragged_query {
+        transform.translation = cursor.extend(transform.translation.z);
+    }
+}
+
+fn end_drag(
+    action_state: Res<ActionState>,
+    dragged_query: Query<Entity, With<Dragged>>,
+    mut cmd: Commands,
+) {
+    if !action_state.just_released(Action::Select) {
+        return;
+    }
+
+    for entity in &dragged_query {
+        cmd.entity(entity).remove::<Dragged>().insert(Dropped);
+    }
+}
+
+fn clear_dropped(dropped_query: Query<Entity, With<Dropped>>, mut cmd: Commands) {
+    for entity in &dropped_query {
+        cmd.entity(entity).remove::<Dropped>();
+    }
+}
+
+fn cancel_order(
+    action_state: Res<ActionState>,
+    selected_query: Query<Entity, With<Selected>>,
+    dragged_query: Query<Entity, With<Dragged>>,
+    mut cmd: Commands,
+) {
+    if !action_state.just_pressed(Action::CancelOrder) {
+        return;
+    }
+
+    for entity in &selected_query {
+        cmd.entity(entity).remove::<(MoveTo, Path)>();
+    }
+
+    for entity in &dragged_query {
+        cmd.entity(entity).remove::<Dragged>();
+    }
+}
+
+fn rasterize_nav_grid(mut nav_grid: ResMut<NavGrid>, obstacles_query: Query<(&Transform, &Obstacle)>) {
+    nav_grid.walkable.fill(true);
+
+    for (transform, obstacle) in &obstacles_query {
+        let center = transform.translation.xy();
+        let min = NavGrid::world_to_cell(center - obstacle.half_extents);
+        let max = NavGrid::world_to_cell(center + obstacle.half_extents);
+
+        for y in min.y.max(0)..=max.y.min(NAV_GRID_HEIGHT as i32 - 1) {
+            for x in min.x.max(0)..=max.x.min(NAV_GRID_WIDTH as i32 - 1) {
+                if let Some(index) = NavGrid::index(IVec2::new(x, y)) {
+                    nav_grid.walkable[index] = false;
+                }
+            }
+        }
+    }
+}
+
+fn issue_move_orders(
+    mut move_to_event_reader: EventReader<MoveToEvent>,
+    selected_query: Query<(Entity, &Transform), With<Selected>>,
+    nav_grid: Res<NavGrid>,
+    formation_kind: Res<FormationKind>,
+    mut cmd: Commands,
+) {
+    let Some(MoveToEvent(move_to)) = move_to_event_reader.read().next() else {
+        return;
+    };
+
+    let units: Vec<(Entity, Vec2)> = selected_query
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation.xy()))
+        .collect();
+
+    if units.is_empty() {
+        return;
+    }
+
+    // A single unit skips formation entirely and targets the order point itself (already
+    // grid-snapped in `handle_input` when snapping is on).
+    let targets = if units.len() == 1 {
+        vec![move_to.0]
+    } else {
+        let positions: Vec<Vec2> = units.iter().map(|(_, position)| *position).collect();
+        let slots: Vec<Vec2> = formation_offsets(*formation_kind, units.len())
+            .into_iter()
+            .map(|offset| move_to.0 + offset)
+            .collect();
+
+        assign_formation_slots(&positions, &slots)
+    };
+
+    for ((entity, current), target) in units.into_iter().zip(targets) {
+        // The group anchor is already snapped in `handle_input`; re-snapping per-unit
+        // targets here would collapse formation offsets smaller than half a grid cell
+        // back onto the same intersection. Just correct the nav-cell quantization below
+        // without re-quantizing to the display lattice.
+        let goal_cell = nav_grid.nearest_walkable(NavGrid::world_to_cell(target));
+        let start_cell = nav_grid.nearest_walkable(NavGrid::world_to_cell(current));
+
+        let Some(path) = find_path(&nav_grid, start_cell, goal_cell) else {
+            continue;
+        };
+
+        let simplified = simplify_path(&nav_grid, &path);
+        let mut waypoints: VecDeque<Vec2> =
+            simplified.into_iter().map(NavGrid::cell_to_world).collect();
+
+        if let Some(last) = waypoints.back_mut() {
+            *last = target;
+        }
+
+        let Some(first) = waypoints.pop_front() else {
+            continue;
+        };
+
+        cmd.entity(entity).insert((MoveTo(first), Path(waypoints)));
+    }
+}
+
+fn move_units(
+    mut movable_query: Query<(Entity, &mut Transform, &MoveTo, &TroopVelocity, Option<&mut Path>)>,
+    time: Res<Time>,
+    mut cmd: Commands,
+) {
+    for (entity, mut transform, move_to, velocity, mut path) in &mut movable_query {
+        let current = transform.translation.xy();
+        let to_target = move_to.0 - current;
+        let distance = to_target.length();
+
+        if distance <= DISTANCE_TOLERANCE {
+            let next_waypoint = path.as_mut().and_then(|path| path.0.pop_front());
+
+            match next_waypoint {
+                Some(next) => cmd.entity(entity).insert(MoveTo(next)),
+                None => cmd.entity(entity).remove::<(MoveTo, Path)>(),
+            };
+
+            continue;
+        }
+
+        let step = (velocity.0 * time.delta_seconds()).min(distance);
+        transform.translation += (to_target.normalize() * step).extend(0.);
+    }
+}
+
 fn select_entities(
     mut finished_selecting_event_reader: EventReader<FinishedSelectingEvent>,
     entities_query: Query<(&Transform, Entity), With<Selectable>>,
+    action_state: Res<ActionState>,
     mut cmd: Commands,
 ) {
     let Some(finished_selection_box) = finished_selecting_event_reader.read().next() else {
         return;
     };
 
+    if !action_state.pressed(Action::AddToSelection) {
+        for (_, entity) in &entities_query {
+            cmd.entity(entity).remove::<(Selected, Border)>();
+        }
+    }
+
     for (transform, entity) in &entities_query {
         let is_inside_selection = finished_selection_box
             .0
@@ -185,8 +1012,12 @@ fn display_selection_box(
     selection_box_display_transform.translation = Vec3::new(center.x, center.y, 0.)
 }
 
-fn display_border(mut gizmos: Gizmos, transform_query: Query<&Transform, With<Border>>) {
-    for transform in &transform_query {
+fn display_border(
+    mut gizmos: Gizmos,
+    border_query: Query<&Transform, With<Border>>,
+    hovered_query: Query<&Transform, (With<Hovered>, Without<Selected>)>,
+) {
+    for transform in &border_query {
         gizmos.ellipse_2d(
             transform.translation.xy(),
             0.,
@@ -194,6 +1025,15 @@ fn display_border(mut gizmos: Gizmos, transform_query: Query<&Transform, With<Bo
             Color::WHITE,
         );
     }
+
+    for transform in &hovered_query {
+        gizmos.ellipse_2d(
+            transform.translation.xy(),
+            0.,
+            Vec2::new(CIRCLE_RADIUS + BORDER_OFFSET, CIRCLE_RADIUS + BORDER_OFFSET),
+            Color::linear_rgba(1.0, 1.0, 0.0, 1.0),
+        );
+    }
 }
 
 fn deselect(
@@ -216,12 +1056,37 @@ fn main() {
         .add_plugins(WorldInspectorPlugin::new())
         .add_event::<FinishedSelectingEvent>()
         .add_event::<DeselectEvent>()
+        .add_event::<MoveToEvent>()
         .init_resource::<SelectionBox>()
+        .init_resource::<NavGrid>()
+        .init_resource::<Bindings>()
+        .init_resource::<ActionState>()
+        .register_type::<GridSettings>()
+        .init_resource::<GridSettings>()
+        .register_type::<FormationKind>()
+        .init_resource::<FormationKind>()
         .add_systems(Startup, setup)
-        .add_systems(Update, handle_mouse_input)
+        .add_systems(Update, camera_controller)
+        .add_systems(Update, update_action_state.after(camera_controller))
+        .add_systems(Update, handle_input.after(update_action_state).before(end_drag))
+        .add_systems(Update, toggle_grid.after(update_action_state))
+        .add_systems(Update, draw_grid)
+        .add_systems(Update, update_hover.after(update_action_state))
+        .add_systems(Update, begin_drag.after(update_hover))
+        .add_systems(
+            Update,
+            cancel_selection_box_on_drag.after(handle_input).after(begin_drag),
+        )
+        .add_systems(Update, drag_entities.after(update_action_state))
+        .add_systems(Update, end_drag.after(update_action_state))
+        .add_systems(Update, cancel_order.after(update_action_state))
+        .add_systems(Update, clear_dropped)
         .add_systems(Update, display_selection_box)
         .add_systems(Update, display_border)
         .add_systems(Update, deselect)
-        .add_systems(Update, select_entities)
+        .add_systems(Update, select_entities.after(update_action_state))
+        .add_systems(Update, rasterize_nav_grid)
+        .add_systems(Update, issue_move_orders.after(rasterize_nav_grid))
+        .add_systems(Update, move_units)
         .run();
 }